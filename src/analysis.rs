@@ -0,0 +1,138 @@
+//! Parsing of the UCI `info` line into a typed [`Analysis`].
+
+/// The engine's evaluation of a position, as reported by the `score` token of an `info` line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Score {
+    /// Score in centipawns, from the engine's point of view.
+    Cp(i32),
+    /// Distance to mate in moves (negative if the engine is the one getting mated).
+    Mate(i32),
+}
+
+/// A parsed `info` line, together with the best move it led to.
+///
+/// Fields the engine didn't report for a given line are left as `None`. When an engine
+/// sends several `info` lines for a search, only the most recent one is kept.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Analysis {
+    /// Search depth in plies.
+    pub depth: Option<u32>,
+    /// Selective search depth in plies.
+    pub seldepth: Option<u32>,
+    /// Index of the principal variation, for engines searching multiple lines at once.
+    pub multipv: Option<u32>,
+    /// The engine's evaluation of the position.
+    pub score: Option<Score>,
+    /// Whether `score` is only a lower bound.
+    pub lowerbound: bool,
+    /// Whether `score` is only an upper bound.
+    pub upperbound: bool,
+    /// Number of nodes searched so far.
+    pub nodes: Option<u64>,
+    /// Search speed, in nodes per second.
+    pub nps: Option<u64>,
+    /// Hash table fill level, in permille.
+    pub hashfull: Option<u32>,
+    /// Number of tablebase hits.
+    pub tbhits: Option<u64>,
+    /// Time spent searching, in milliseconds.
+    pub time: Option<u64>,
+    /// The principal variation, as a list of moves in coordinate notation.
+    pub pv: Vec<String>,
+    /// The best move found once the search completes.
+    pub best_move: String,
+}
+
+impl Score {
+    fn from_tokens<'a>(mut tokens: impl Iterator<Item = &'a str>) -> Option<Score> {
+        match tokens.next()? {
+            "cp" => Some(Score::Cp(tokens.next()?.parse().ok()?)),
+            "mate" => Some(Score::Mate(tokens.next()?.parse().ok()?)),
+            _ => None,
+        }
+    }
+}
+
+/// Parses a single `info` line into an [`Analysis`], leaving `best_move` empty.
+///
+/// Unrecognized tokens (including any we don't otherwise handle) are skipped.
+pub(crate) fn parse_info_line(line: &str) -> Analysis {
+    let mut analysis = Analysis::default();
+    let mut tokens = line.split_whitespace().peekable();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            "depth" => analysis.depth = next(&mut tokens),
+            "seldepth" => analysis.seldepth = next(&mut tokens),
+            "multipv" => analysis.multipv = next(&mut tokens),
+            "nodes" => analysis.nodes = next(&mut tokens),
+            "nps" => analysis.nps = next(&mut tokens),
+            "hashfull" => analysis.hashfull = next(&mut tokens),
+            "tbhits" => analysis.tbhits = next(&mut tokens),
+            "time" => analysis.time = next(&mut tokens),
+            "score" => analysis.score = Score::from_tokens(&mut tokens),
+            "lowerbound" => analysis.lowerbound = true,
+            "upperbound" => analysis.upperbound = true,
+            "pv" => {
+                analysis.pv = tokens.by_ref().map(str::to_string).collect();
+                break;
+            }
+            // The rest of the line is arbitrary free text, not further tokens to parse.
+            "string" => break,
+            _ => {}
+        }
+    }
+
+    analysis
+}
+
+fn next<T: std::str::FromStr>(tokens: &mut std::iter::Peekable<std::str::SplitWhitespace>) -> Option<T> {
+    tokens.next()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_depth_nodes_and_cp_score() {
+        let analysis = parse_info_line("info depth 12 seldepth 20 nodes 12345 nps 987654 score cp 34 time 500");
+        assert_eq!(analysis.depth, Some(12));
+        assert_eq!(analysis.seldepth, Some(20));
+        assert_eq!(analysis.nodes, Some(12345));
+        assert_eq!(analysis.nps, Some(987654));
+        assert_eq!(analysis.score, Some(Score::Cp(34)));
+        assert_eq!(analysis.time, Some(500));
+    }
+
+    #[test]
+    fn parses_mate_score() {
+        let analysis = parse_info_line("info depth 5 score mate -3");
+        assert_eq!(analysis.score, Some(Score::Mate(-3)));
+    }
+
+    #[test]
+    fn parses_bound_flags() {
+        let analysis = parse_info_line("info depth 5 score cp 10 lowerbound");
+        assert!(analysis.lowerbound);
+        assert!(!analysis.upperbound);
+    }
+
+    #[test]
+    fn parses_pv_as_rest_of_line() {
+        let analysis = parse_info_line("info depth 5 score cp 10 pv e2e4 e7e5 g1f3");
+        assert_eq!(analysis.pv, vec!["e2e4", "e7e5", "g1f3"]);
+    }
+
+    #[test]
+    fn ignores_unrecognized_tokens() {
+        let analysis = parse_info_line("info string this engine says hello");
+        assert_eq!(analysis, Analysis::default());
+    }
+
+    #[test]
+    fn treats_string_as_free_text_even_with_colliding_keywords() {
+        let analysis = parse_info_line("info string depth score nodes 5 pv e2e4");
+        assert_eq!(analysis, Analysis::default());
+    }
+}
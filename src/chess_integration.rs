@@ -0,0 +1,95 @@
+//! Optional integration with the [`chess`] crate, enabled via the `chess` feature.
+//!
+//! This lets callers drive an [`Engine`] with typed [`Board`]/[`ChessMove`] values instead of
+//! raw UCI strings, trading stringly-typed FENs and coordinate notation for compile-time checked
+//! positions and moves.
+
+use std::str::FromStr;
+
+use chess::{Board, ChessMove, Game};
+
+use crate::{Engine, EngineError, Result, SearchParams};
+
+impl Engine {
+    /// Sets the position to the given [`Board`], equivalent to [`set_position`](Engine::set_position)
+    /// but using the board's FEN directly.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EngineError` if there's an error while communicating with the engine.
+    pub fn set_board(&self, board: &Board) -> Result<()> {
+        self.set_position(&board.to_string())
+    }
+
+    /// Sets the position to the given [`Board`] and then plays the given moves.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EngineError` if there's an error while communicating with the engine.
+    pub fn make_moves_from_board(&self, board: &Board, moves: &[ChessMove]) -> Result<()> {
+        let moves = to_coordinate_notation(moves);
+        self.make_moves_from_position(&board.to_string(), &moves)
+    }
+
+    /// Asks the engine to play the given moves from the initial position, given as typed
+    /// [`ChessMove`]s instead of coordinate-notation strings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EngineError` if there's an error while communicating with the engine.
+    pub fn make_chess_moves(&self, moves: &[ChessMove]) -> Result<()> {
+        self.make_moves(&to_coordinate_notation(moves))
+    }
+
+    /// Returns the best move in the current position, parsed into a [`ChessMove`] and validated
+    /// against `board`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError::IllegalMove` if the move the engine returned isn't legal on `board`,
+    /// in addition to the usual errors from [`bestmove`](Engine::bestmove).
+    pub fn bestmove_chess(&self, board: &Board) -> Result<ChessMove> {
+        parse_move(board, &self.bestmove()?)
+    }
+
+    /// Like [`ponder`](Engine::ponder), but parses the best move and, if the engine suggested
+    /// one, the ponder move into [`ChessMove`]s validated against `board`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError::IllegalMove` if either move the engine returned isn't legal in its
+    /// position, in addition to the usual errors from [`ponder`](Engine::ponder).
+    pub fn ponder_chess(&self, board: &Board, params: &SearchParams) -> Result<(ChessMove, Option<ChessMove>)> {
+        let (mv, ponder) = self.ponder(params)?;
+        let mv = parse_move(board, &mv)?;
+
+        let ponder = ponder
+            .map(|ponder| parse_move(&board.make_move_new(mv), &ponder))
+            .transpose()?;
+
+        Ok((mv, ponder))
+    }
+
+    /// Plays the engine's chosen move in `game`, the current position, and returns it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EngineError::IllegalMove` if the move the engine returned isn't legal in `game`'s
+    /// current position, in addition to the usual errors from [`bestmove`](Engine::bestmove).
+    pub fn play_bestmove(&self, game: &mut Game) -> Result<ChessMove> {
+        let mv = self.bestmove_chess(&game.current_position())?;
+        game.make_move(mv);
+        Ok(mv)
+    }
+}
+
+fn to_coordinate_notation(moves: &[ChessMove]) -> Vec<String> {
+    moves.iter().map(ChessMove::to_string).collect()
+}
+
+fn parse_move(board: &Board, mv: &str) -> Result<ChessMove> {
+    ChessMove::from_str(mv)
+        .ok()
+        .filter(|mv| board.legal(*mv))
+        .ok_or_else(|| EngineError::IllegalMove(mv.to_string()))
+}
@@ -1,21 +1,48 @@
 #[macro_use]
 extern crate log;
 
-use std::process::{Child, Command, Stdio};
+mod analysis;
+#[cfg(feature = "chess")]
+mod chess_integration;
+mod message;
+mod option;
+mod search;
+
+pub use analysis::{Analysis, Score};
+pub use message::Message;
+pub use option::{EngineOption, OptionKind};
+pub use search::SearchParams;
+
+use std::process::{Child, ChildStdin, Command, Stdio};
 
 use std::io::Write;
-use std::io::{self, Read};
+use std::io;
 
 use std::fmt;
-use std::thread;
-use std::time::Duration;
 
-use std::cell::RefCell;
+use std::sync::mpsc;
+use std::sync::mpsc::Receiver;
+use std::sync::Mutex;
+use std::time::Duration;
 
+/// A running UCI engine.
+///
+/// `stdin` and `receiver` are each guarded by their own [`Mutex`] so that a thread blocked
+/// waiting on a [`recv`](Engine::recv) (e.g. inside [`ponder`](Engine::ponder)) doesn't prevent
+/// another thread from writing a command in the meantime (e.g. [`ponderhit`](Engine::ponderhit)
+/// or [`stop`](Engine::stop)) — the two mutexes are independent, so writing never waits on the
+/// in-flight read.
 pub struct Engine {
-    engine: RefCell<Child>,
+    child: Child,
+    stdin: Mutex<ChildStdin>,
+    receiver: Mutex<Receiver<Message>>,
+
+    name: Option<String>,
+    author: Option<String>,
+    options: Vec<EngineOption>,
 
     movetime: u32,
+    read_timeout: Option<Duration>,
 }
 
 const DEFAULT_TIME: u32 = 100;
@@ -37,23 +64,64 @@ impl Engine {
     ///
     /// [`Engine`]: struct.Engine.html
     pub fn new(path: &str) -> Result<Engine> {
-        let cmd = Command::new(path)
+        let mut child = Command::new(path)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .spawn()
             .expect("Unable to run engine");
 
-        let res = Engine {
-            engine: RefCell::new(cmd),
+        let stdin = child.stdin.take().expect("Engine has no stdin");
+        let stdout = child.stdout.take().expect("Engine has no stdout");
+        let receiver = message::spawn_reader(stdout);
+
+        let mut res = Engine {
+            child,
+            stdin: Mutex::new(stdin),
+            receiver: Mutex::new(receiver),
+            name: None,
+            author: None,
+            options: vec![],
             movetime: DEFAULT_TIME,
+            read_timeout: None,
         };
 
-        res.read_line()?;
-        res.command("uci")?;
+        res.write_fmt(format_args!("uci\n"))?;
+        loop {
+            match res.recv()? {
+                Message::UciOk => break,
+                Message::Id(line) => {
+                    if let Some(name) = line.strip_prefix("name ") {
+                        res.name = Some(name.to_string());
+                    } else if let Some(author) = line.strip_prefix("author ") {
+                        res.author = Some(author.to_string());
+                    }
+                }
+                Message::Option(option) => res.options.push(option),
+                _ => {}
+            }
+        }
 
         Ok(res)
     }
 
+    /// The engine's name, as reported by its `id name` line during the handshake.
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The engine's author, as reported by its `id author` line during the handshake.
+    #[must_use]
+    pub fn author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
+
+    /// The UCI options this engine declared during the handshake.
+    #[must_use]
+    pub fn available_options(&self) -> &[EngineOption] {
+        &self.options
+    }
+
     /// Changes the amount of time the engine spends looking for a move
     ///
     /// # Arguments
@@ -65,6 +133,23 @@ impl Engine {
         self
     }
 
+    /// Sets a timeout for waiting on messages from the engine. If a call is waiting and no
+    /// message at all arrives from the engine within this duration, the call returns
+    /// `EngineError::Timeout` instead of blocking forever. The timeout applies to each message
+    /// separately, so a call that keeps receiving other messages (such as `info` lines) can
+    /// wait longer than this in total.
+    ///
+    /// By default there's no timeout.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum time to wait for a single message from the engine.
+    #[must_use]
+    pub fn read_timeout(mut self, timeout: Duration) -> Engine {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
     /// Asks the engine to play the given moves from the initial position on it's internal board.
     ///
     /// # Arguments
@@ -126,12 +211,149 @@ impl Engine {
     /// Returns an error if the engine is not ready to return a move
     pub fn bestmove(&self) -> Result<String> {
         self.write_fmt(format_args!("go movetime {}\n", self.movetime))?;
-        loop {
-            let s = self.read_line()?;
-            debug!("{}", s);
-            if s.starts_with("bestmove") {
-                return Ok(s.split(' ').collect::<Vec<&str>>()[1].trim().to_string());
+        self.wait_for_bestmove()
+    }
+
+    /// Starts a search like [`bestmove`](Engine::bestmove), but parses every `info` line the
+    /// engine emits instead of discarding them, returning the last one alongside the best move.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EngineError` if there's an error while communicating with the engine.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let engine = uci::Engine::new("stockfish").unwrap();
+    /// let analysis = engine.analyze().unwrap();
+    /// println!("{} {:?}", analysis.best_move, analysis.score);
+    /// ```
+    pub fn analyze(&self) -> Result<Analysis> {
+        self.write_fmt(format_args!("go movetime {}\n", self.movetime))?;
+        self.wait_for_bestmove_with_analysis()
+    }
+
+    /// Starts a search with the given [`SearchParams`], covering the full UCI `go` vocabulary
+    /// (time controls, fixed depth/nodes, mate search, or [`infinite`](SearchParams::infinite)).
+    ///
+    /// An infinite search only ends when [`stop`](Engine::stop) is called.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EngineError` if there's an error while communicating with the engine.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uci::SearchParams;
+    ///
+    /// let engine = uci::Engine::new("stockfish").unwrap();
+    /// let params = SearchParams::new().depth(10);
+    /// let bestmove = engine.go(&params).unwrap();
+    /// ```
+    pub fn go(&self, params: &SearchParams) -> Result<String> {
+        self.write_fmt(format_args!("{}\n", params.to_command()))?;
+        self.wait_for_bestmove()
+    }
+
+    /// Like [`go`](Engine::go), but parses every `info` line the engine emits instead of
+    /// discarding them, returning the last one alongside the best move.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EngineError` if there's an error while communicating with the engine.
+    pub fn analyze_with(&self, params: &SearchParams) -> Result<Analysis> {
+        self.write_fmt(format_args!("{}\n", params.to_command()))?;
+        self.wait_for_bestmove_with_analysis()
+    }
+
+    /// Stops an ongoing [`infinite`](SearchParams::infinite) search and returns the best move
+    /// the engine had found.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EngineError` if there's an error while communicating with the engine.
+    pub fn stop(&self) -> Result<String> {
+        self.write_fmt(format_args!("stop\n"))?;
+        self.wait_for_bestmove()
+    }
+
+    /// Starts a pondering search with the given [`SearchParams`] (built with
+    /// [`SearchParams::ponder`]), and returns the best move found together with the move the
+    /// engine wants to ponder on next, if it suggested one.
+    ///
+    /// This call blocks until the engine produces a `bestmove`. To convert the ongoing ponder
+    /// search into a normal one, call [`ponderhit`](Engine::ponderhit) from another thread while
+    /// this call is in flight — `Engine` is `Sync` for exactly this reason, so it can be shared
+    /// behind an `Arc` and used from both threads at once.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EngineError` if there's an error while communicating with the engine.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::sync::Arc;
+    /// use std::thread;
+    /// use uci::SearchParams;
+    ///
+    /// let engine = Arc::new(uci::Engine::new("stockfish").unwrap());
+    ///
+    /// let other = Arc::clone(&engine);
+    /// let ponderhit = thread::spawn(move || other.ponderhit().unwrap());
+    ///
+    /// let (bestmove, ponder) = engine.ponder(&SearchParams::new().ponder()).unwrap();
+    /// ponderhit.join().unwrap();
+    /// ```
+    pub fn ponder(&self, params: &SearchParams) -> Result<(String, Option<String>)> {
+        self.write_fmt(format_args!("{}\n", params.to_command()))?;
+        self.wait_for_bestmove_with_ponder()
+    }
+
+    /// Tells the engine that its ponder move was played, converting an ongoing ponder search
+    /// into a normal one.
+    ///
+    /// Safe to call from another thread while a [`ponder`](Engine::ponder) call is blocked
+    /// waiting for a `bestmove` on this `Engine`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EngineError` if there's an error while communicating with the engine.
+    pub fn ponderhit(&self) -> Result<()> {
+        self.write_fmt(format_args!("ponderhit\n"))
+    }
+
+    /// Limits the engine's playing strength to roughly the given Elo rating, or disables any
+    /// limit when passed `None`, via the standard `UCI_LimitStrength`/`UCI_Elo` options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EngineError` if the engine doesn't support strength limiting, or if `elo`
+    /// falls outside the range advertised by its `UCI_Elo` option.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let engine = uci::Engine::new("stockfish").unwrap();
+    /// engine.set_strength(Some(1500)).unwrap();
+    /// ```
+    pub fn set_strength(&self, elo: Option<u32>) -> Result<()> {
+        match elo {
+            Some(elo) => {
+                let elo = elo.to_string();
+                // Validate against the declared range before touching any engine state, so a
+                // bad Elo can't leave UCI_LimitStrength enabled with no UCI_Elo set.
+                self.validate_option("UCI_Elo", &elo)?;
+
+                self.set_option("UCI_LimitStrength", "true")?;
+                if let Err(err) = self.set_option("UCI_Elo", &elo) {
+                    let _ = self.set_option("UCI_LimitStrength", "false");
+                    return Err(err);
+                }
+                Ok(())
             }
+            None => self.set_option("UCI_LimitStrength", "false"),
         }
     }
 
@@ -144,7 +366,8 @@ impl Engine {
     ///
     /// # Errors
     ///
-    /// Returns an `EngineError` if the engine doesn't support the option
+    /// Returns `EngineError::UnknownOption` if the engine doesn't have an option by this name, or
+    /// `EngineError::InvalidOptionValue` if `value` doesn't fit the option's declared type or range.
     ///
     /// # Examples
     ///
@@ -153,6 +376,8 @@ impl Engine {
     /// engine.set_option("Skill Level", "5").unwrap();
     /// ```
     pub fn set_option(&self, name: &str, value: &str) -> Result<()> {
+        self.validate_option(name, value)?;
+
         self.write_fmt(format_args!("setoption name {name} value {value}\n"))?;
         let error_msg = self.read_left_output()?;
 
@@ -163,6 +388,45 @@ impl Engine {
         }
     }
 
+    fn validate_option(&self, name: &str, value: &str) -> Result<()> {
+        let invalid = |reason: &str| {
+            Err(EngineError::InvalidOptionValue {
+                name: name.to_string(),
+                reason: reason.to_string(),
+            })
+        };
+
+        let option = self
+            .options
+            .iter()
+            .find(|option| option.name == name)
+            .ok_or_else(|| EngineError::UnknownOption(name.to_string()))?;
+
+        match &option.kind {
+            OptionKind::Check { .. } => {
+                if value != "true" && value != "false" {
+                    return invalid("expected 'true' or 'false'");
+                }
+            }
+            OptionKind::Spin { min, max, .. } => {
+                let Ok(value) = value.parse::<i64>() else {
+                    return invalid("expected an integer");
+                };
+                if value < *min || value > *max {
+                    return invalid(&format!("value {value} is outside of range {min}..={max}"));
+                }
+            }
+            OptionKind::Combo { vars, .. } => {
+                if !vars.iter().any(|var| var == value) {
+                    return invalid(&format!("value must be one of {vars:?}"));
+                }
+            }
+            OptionKind::Button | OptionKind::String { .. } => {}
+        }
+
+        Ok(())
+    }
+
     /// Sends a command to the engine and returns the output
     ///
     /// # Errors
@@ -178,52 +442,79 @@ impl Engine {
     /// ```
     pub fn command(&self, cmd: &str) -> Result<String> {
         self.write_fmt(format_args!("{}\n", cmd.trim()))?;
-        thread::sleep(Duration::from_millis(100));
         self.read_left_output()
     }
 
+    fn wait_for_bestmove(&self) -> Result<String> {
+        loop {
+            if let Message::BestMove { mv, .. } = self.recv()? {
+                return Ok(mv);
+            }
+        }
+    }
+
+    fn wait_for_bestmove_with_analysis(&self) -> Result<Analysis> {
+        let mut analysis = Analysis::default();
+        loop {
+            match self.recv()? {
+                Message::Info(info) => analysis = info,
+                Message::BestMove { mv, .. } => {
+                    analysis.best_move = mv;
+                    return Ok(analysis);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn wait_for_bestmove_with_ponder(&self) -> Result<(String, Option<String>)> {
+        loop {
+            if let Message::BestMove { mv, ponder } = self.recv()? {
+                return Ok((mv, ponder));
+            }
+        }
+    }
+
     fn read_left_output(&self) -> Result<String> {
         let mut s: Vec<String> = vec![];
 
         self.write_fmt(format_args!("isready\n"))?;
         loop {
-            let next_line = self.read_line()?;
-            match next_line.trim() {
-                "readyok" => return Ok(s.join("\n")),
-                other => s.push(other.to_string()),
+            match self.recv()? {
+                Message::ReadyOk => return Ok(s.join("\n")),
+                Message::Raw(line) => s.push(line),
+                Message::Id(line) => s.push(format!("id {line}")),
+                Message::Option(option) => s.push(format!("option name {}", option.name)),
+                Message::Info(_) | Message::BestMove { .. } | Message::UciOk => {}
             }
         }
     }
 
     fn write_fmt(&self, args: fmt::Arguments) -> Result<()> {
         info!("Command: {:?}", fmt::format(args));
-        self.engine
-            .borrow_mut()
-            .stdin
-            .as_mut()
-            .unwrap()
-            .write_fmt(args)?;
+        self.stdin.lock().unwrap().write_fmt(args)?;
         Ok(())
     }
 
-    fn read_line(&self) -> Result<String> {
-        let mut s = String::new();
-        let mut buf: Vec<u8> = vec![0];
+    fn recv(&self) -> Result<Message> {
+        let receiver = self.receiver.lock().unwrap();
+        let message = match self.read_timeout {
+            Some(timeout) => receiver.recv_timeout(timeout).map_err(|err| match err {
+                mpsc::RecvTimeoutError::Timeout => EngineError::Timeout,
+                mpsc::RecvTimeoutError::Disconnected => EngineError::Disconnected,
+            })?,
+            None => receiver.recv().map_err(|_| EngineError::Disconnected)?,
+        };
+        debug!("{:?}", message);
+        Ok(message)
+    }
+}
 
-        loop {
-            let _ = self
-                .engine
-                .borrow_mut()
-                .stdout
-                .as_mut()
-                .unwrap()
-                .read(&mut buf)?;
-            s.push(buf[0] as char);
-            if buf[0] == b'\n' {
-                break;
-            }
-        }
-        Ok(s)
+impl Drop for Engine {
+    /// Kills the engine process so it isn't left running after the `Engine` is dropped.
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
     }
 }
 
@@ -235,14 +526,39 @@ pub enum EngineError {
 
     /// Engine doesn't recognize the specified option.
     UnknownOption(String),
+
+    /// The value given for an option doesn't fit its declared type or range.
+    InvalidOptionValue {
+        /// Name of the option.
+        name: String,
+        /// Why the value was rejected.
+        reason: String,
+    },
+
+    /// The engine's reader thread exited, usually because the engine process quit.
+    Disconnected,
+
+    /// No message arrived from the engine within the configured [`read_timeout`](Engine::read_timeout).
+    Timeout,
+
+    /// The engine returned a move that isn't legal in the given position.
+    #[cfg(feature = "chess")]
+    IllegalMove(String),
 }
 
-use self::EngineError::{Io, UnknownOption};
+use self::EngineError::{Disconnected, Io, UnknownOption};
 impl fmt::Display for EngineError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             Io(ref err) => write!(f, "IO error: {err}"),
             UnknownOption(ref option) => write!(f, "No such option: '{option}'"),
+            EngineError::InvalidOptionValue { ref name, ref reason } => {
+                write!(f, "Invalid value for option '{name}': {reason}")
+            }
+            Disconnected => write!(f, "Engine disconnected"),
+            EngineError::Timeout => write!(f, "Timed out waiting for the engine"),
+            #[cfg(feature = "chess")]
+            EngineError::IllegalMove(ref mv) => write!(f, "Illegal move: '{mv}'"),
         }
     }
 }
@@ -270,4 +586,59 @@ mod tests {
 
         println!("{t}");
     }
+
+    fn assert_sync<T: Sync>() {}
+
+    #[test]
+    fn engine_is_sync() {
+        assert_sync::<Engine>();
+    }
+
+    // Stands in for a real UCI engine: a tiny shell script that replies just enough to drive a
+    // ponder search, so the test below doesn't depend on a real engine binary being installed.
+    #[cfg(unix)]
+    fn write_fake_engine() -> std::path::PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let script = "#!/bin/sh\n\
+            while IFS= read -r line; do\n\
+              case \"$line\" in\n\
+                uci) printf 'uciok\\n' ;;\n\
+                isready) printf 'readyok\\n' ;;\n\
+                go*ponder*) sleep 0.2; printf 'bestmove e2e4 ponder e7e5\\n' ;;\n\
+                go*) printf 'bestmove e2e4\\n' ;;\n\
+                *) : ;;\n\
+              esac\n\
+            done\n";
+
+        let path = std::env::temp_dir().join(format!("uci-rs-fake-engine-{}.sh", std::process::id()));
+        std::fs::write(&path, script).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn ponder_then_ponderhit_from_another_thread() {
+        use std::sync::Arc;
+        use std::thread;
+
+        let path = write_fake_engine();
+        let engine = Arc::new(Engine::new(path.to_str().unwrap()).unwrap());
+
+        let other = Arc::clone(&engine);
+        // Give `ponder` time to send its `go` first; the fake engine takes 200 ms to answer it.
+        let ponderhit = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            other.ponderhit().unwrap();
+        });
+
+        let (mv, ponder) = engine.ponder(&SearchParams::new().ponder()).unwrap();
+        ponderhit.join().unwrap();
+
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(mv, "e2e4");
+        assert_eq!(ponder.as_deref(), Some("e7e5"));
+    }
 }
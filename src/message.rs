@@ -0,0 +1,137 @@
+//! Background parsing of the engine's stdout into typed [`Message`]s.
+
+use std::io::{self, BufRead, BufReader};
+use std::process::ChildStdout;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+use crate::analysis::{self, Analysis};
+use crate::option::{self, EngineOption};
+
+/// A single message parsed from a line of the engine's stdout.
+#[derive(Debug, Clone)]
+pub enum Message {
+    /// A parsed `info` line.
+    Info(Analysis),
+    /// A `bestmove` line, with the optional `ponder` move the engine suggests pondering on.
+    BestMove { mv: String, ponder: Option<String> },
+    /// A parsed `option` line declaring an engine-specific option.
+    Option(EngineOption),
+    /// The remainder of an `id` line, e.g. `name Stockfish 15` or `author the Stockfish developers`.
+    Id(String),
+    /// The engine finished replying to a `uci` command.
+    UciOk,
+    /// The engine finished replying to an `isready` command.
+    ReadyOk,
+    /// Any other line the engine sent, verbatim.
+    Raw(String),
+}
+
+/// Spawns a thread that owns `stdout`, parses each line the engine emits into a [`Message`],
+/// and forwards it over the returned channel. The thread exits once the engine closes stdout.
+pub(crate) fn spawn_reader(stdout: ChildStdout) -> Receiver<Message> {
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        let mut reader = BufReader::new(stdout);
+        loop {
+            let line = match read_line(&mut reader) {
+                Ok(line) if line.is_empty() => break,
+                Ok(line) => line,
+                Err(_) => break,
+            };
+
+            if tx.send(parse_message(line.trim())).is_err() {
+                break;
+            }
+        }
+    });
+
+    rx
+}
+
+fn parse_message(line: &str) -> Message {
+    if line.starts_with("info") {
+        Message::Info(analysis::parse_info_line(line))
+    } else if let Some(rest) = line.strip_prefix("bestmove") {
+        let mut parts = rest.split_whitespace();
+        let mv = parts.next().unwrap_or("").to_string();
+        let ponder = (parts.next() == Some("ponder"))
+            .then(|| parts.next())
+            .flatten()
+            .map(str::to_string);
+        Message::BestMove { mv, ponder }
+    } else if line == "uciok" {
+        Message::UciOk
+    } else if line == "readyok" {
+        Message::ReadyOk
+    } else if let Some(rest) = line.strip_prefix("id ") {
+        Message::Id(rest.to_string())
+    } else if let Some(rest) = line.strip_prefix("option ") {
+        option::parse_option_line(rest).map_or_else(|| Message::Raw(line.to_string()), Message::Option)
+    } else {
+        Message::Raw(line.to_string())
+    }
+}
+
+/// Reads a line of raw bytes from `reader` and decodes it as UTF-8, replacing any invalid
+/// sequences an engine's `info string` might emit rather than failing outright.
+fn read_line(reader: &mut BufReader<ChildStdout>) -> io::Result<String> {
+    let mut buf: Vec<u8> = Vec::new();
+    reader.read_until(b'\n', &mut buf)?;
+    Ok(String::from_utf8_lossy(&buf).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_info_line() {
+        assert!(matches!(parse_message("info depth 5 score cp 10"), Message::Info(_)));
+    }
+
+    #[test]
+    fn parses_bestmove_without_ponder() {
+        let message = parse_message("bestmove e2e4");
+        assert!(matches!(message, Message::BestMove { mv, ponder: None } if mv == "e2e4"));
+    }
+
+    #[test]
+    fn parses_bestmove_with_ponder() {
+        let message = parse_message("bestmove e2e4 ponder e7e5");
+        assert!(matches!(
+            message,
+            Message::BestMove { mv, ponder: Some(ponder) } if mv == "e2e4" && ponder == "e7e5"
+        ));
+    }
+
+    #[test]
+    fn parses_uciok_and_readyok() {
+        assert!(matches!(parse_message("uciok"), Message::UciOk));
+        assert!(matches!(parse_message("readyok"), Message::ReadyOk));
+    }
+
+    #[test]
+    fn parses_id_line() {
+        let message = parse_message("id name Stockfish 15");
+        assert!(matches!(message, Message::Id(id) if id == "name Stockfish 15"));
+    }
+
+    #[test]
+    fn parses_well_formed_option_line() {
+        let message = parse_message("option name Hash type spin default 16 min 1 max 1024");
+        assert!(matches!(message, Message::Option(option) if option.name == "Hash"));
+    }
+
+    #[test]
+    fn falls_back_to_raw_for_malformed_option_line() {
+        let message = parse_message("option type spin name Hash default 16 min 1 max 1024");
+        assert!(matches!(message, Message::Raw(line) if line.starts_with("option")));
+    }
+
+    #[test]
+    fn falls_back_to_raw_for_unrecognized_line() {
+        assert!(matches!(parse_message("copyprotection checking"), Message::Raw(_)));
+    }
+}
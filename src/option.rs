@@ -0,0 +1,140 @@
+//! Parsing of the UCI `option` line into a typed [`EngineOption`].
+
+/// A UCI option declared by the engine during the `uci` handshake.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EngineOption {
+    /// The option's name, e.g. `"Hash"` or `"Skill Level"`.
+    pub name: String,
+    /// The option's declared type and constraints.
+    pub kind: OptionKind,
+}
+
+/// The declared type and constraints of an [`EngineOption`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OptionKind {
+    /// A boolean option.
+    Check { default: bool },
+    /// An integer option, constrained to `min..=max`.
+    Spin { default: i64, min: i64, max: i64 },
+    /// An option limited to one of a predefined set of string values.
+    Combo { default: String, vars: Vec<String> },
+    /// An action with no value, triggered by sending it as an option name.
+    Button,
+    /// A free-form string option.
+    String { default: String },
+}
+
+/// Parses the remainder of an `option` line (everything after `option `) into an [`EngineOption`].
+///
+/// Returns `None` if the line doesn't follow the expected `name ... type ... [default ...]` shape.
+pub(crate) fn parse_option_line(line: &str) -> Option<EngineOption> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+
+    let name_start = tokens.iter().position(|&t| t == "name")? + 1;
+    let type_pos = tokens.iter().position(|&t| t == "type")?;
+    let name = tokens.get(name_start..type_pos)?.join(" ");
+
+    let kind = match *tokens.get(type_pos + 1)? {
+        "check" => OptionKind::Check {
+            default: segment(&tokens, "default", &["min", "max", "var"]) == "true",
+        },
+        "spin" => OptionKind::Spin {
+            default: segment(&tokens, "default", &["min", "max", "var"]).parse().ok()?,
+            min: segment(&tokens, "min", &["default", "max", "var"]).parse().ok()?,
+            max: segment(&tokens, "max", &["default", "min", "var"]).parse().ok()?,
+        },
+        "combo" => OptionKind::Combo {
+            default: segment(&tokens, "default", &["var"]),
+            vars: tokens
+                .iter()
+                .enumerate()
+                .filter(|&(_, &t)| t == "var")
+                .map(|(i, _)| segment(&tokens[i..], "var", &["var"]))
+                .collect(),
+        },
+        "button" => OptionKind::Button,
+        "string" => OptionKind::String {
+            default: segment(&tokens, "default", &[]),
+        },
+        _ => return None,
+    };
+
+    Some(EngineOption { name, kind })
+}
+
+/// Returns the tokens following `marker` up to (but not including) the next token in `stops`,
+/// joined back into a string.
+fn segment(tokens: &[&str], marker: &str, stops: &[&str]) -> String {
+    let Some(start) = tokens.iter().position(|&t| t == marker).map(|i| i + 1) else {
+        return String::new();
+    };
+    let end = tokens[start..]
+        .iter()
+        .position(|t| stops.contains(t))
+        .map_or(tokens.len(), |i| start + i);
+    tokens[start..end].join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_check_option() {
+        let option = parse_option_line("name Ponder type check default false").unwrap();
+        assert_eq!(option.name, "Ponder");
+        assert_eq!(option.kind, OptionKind::Check { default: false });
+    }
+
+    #[test]
+    fn parses_spin_option() {
+        let option = parse_option_line("name Hash type spin default 16 min 1 max 33554432").unwrap();
+        assert_eq!(option.name, "Hash");
+        assert_eq!(
+            option.kind,
+            OptionKind::Spin { default: 16, min: 1, max: 33554432 }
+        );
+    }
+
+    #[test]
+    fn parses_combo_option() {
+        let option =
+            parse_option_line("name Style type combo default Normal var Solid var Normal var Risky").unwrap();
+        assert_eq!(option.name, "Style");
+        assert_eq!(
+            option.kind,
+            OptionKind::Combo {
+                default: "Normal".to_string(),
+                vars: vec!["Solid".to_string(), "Normal".to_string(), "Risky".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn parses_button_option() {
+        let option = parse_option_line("name Clear Hash type button").unwrap();
+        assert_eq!(option.name, "Clear Hash");
+        assert_eq!(option.kind, OptionKind::Button);
+    }
+
+    #[test]
+    fn parses_string_option() {
+        let option = parse_option_line("name Debug Log File type string default").unwrap();
+        assert_eq!(option.name, "Debug Log File");
+        assert_eq!(option.kind, OptionKind::String { default: String::new() });
+    }
+
+    #[test]
+    fn returns_none_without_panicking_when_type_precedes_name() {
+        assert_eq!(
+            parse_option_line("type spin name Foo default 1 min 0 max 2"),
+            None
+        );
+    }
+
+    #[test]
+    fn returns_none_for_lines_missing_name_or_type() {
+        assert_eq!(parse_option_line("type spin default 1 min 0 max 2"), None);
+        assert_eq!(parse_option_line("name Foo"), None);
+    }
+}
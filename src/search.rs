@@ -0,0 +1,203 @@
+//! The `go` subcommand and its search parameters.
+
+/// Parameters for a `go` command, covering the full UCI `go` vocabulary.
+///
+/// Only the fields that have been set are sent to the engine; build one with
+/// [`SearchParams::new`] and the builder methods below.
+///
+/// # Examples
+///
+/// ```
+/// use uci::SearchParams;
+///
+/// let params = SearchParams::new()
+///     .wtime(300_000)
+///     .btime(300_000)
+///     .winc(2_000)
+///     .binc(2_000)
+///     .movestogo(40);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchParams {
+    wtime: Option<u32>,
+    btime: Option<u32>,
+    winc: Option<u32>,
+    binc: Option<u32>,
+    movestogo: Option<u32>,
+    depth: Option<u32>,
+    nodes: Option<u64>,
+    mate: Option<u32>,
+    movetime: Option<u32>,
+    infinite: bool,
+    ponder: bool,
+}
+
+impl SearchParams {
+    /// Creates an empty set of search parameters.
+    #[must_use]
+    pub fn new() -> SearchParams {
+        SearchParams::default()
+    }
+
+    /// Time left on White's clock, in milliseconds.
+    #[must_use]
+    pub fn wtime(mut self, wtime: u32) -> SearchParams {
+        self.wtime = Some(wtime);
+        self
+    }
+
+    /// Time left on Black's clock, in milliseconds.
+    #[must_use]
+    pub fn btime(mut self, btime: u32) -> SearchParams {
+        self.btime = Some(btime);
+        self
+    }
+
+    /// White's increment per move, in milliseconds.
+    #[must_use]
+    pub fn winc(mut self, winc: u32) -> SearchParams {
+        self.winc = Some(winc);
+        self
+    }
+
+    /// Black's increment per move, in milliseconds.
+    #[must_use]
+    pub fn binc(mut self, binc: u32) -> SearchParams {
+        self.binc = Some(binc);
+        self
+    }
+
+    /// Number of moves left until the next time control.
+    #[must_use]
+    pub fn movestogo(mut self, movestogo: u32) -> SearchParams {
+        self.movestogo = Some(movestogo);
+        self
+    }
+
+    /// Search to a fixed depth, in plies.
+    #[must_use]
+    pub fn depth(mut self, depth: u32) -> SearchParams {
+        self.depth = Some(depth);
+        self
+    }
+
+    /// Search until this many nodes have been visited.
+    #[must_use]
+    pub fn nodes(mut self, nodes: u64) -> SearchParams {
+        self.nodes = Some(nodes);
+        self
+    }
+
+    /// Search for a mate in this many moves.
+    #[must_use]
+    pub fn mate(mut self, mate: u32) -> SearchParams {
+        self.mate = Some(mate);
+        self
+    }
+
+    /// Search for exactly this long, in milliseconds.
+    #[must_use]
+    pub fn movetime(mut self, movetime: u32) -> SearchParams {
+        self.movetime = Some(movetime);
+        self
+    }
+
+    /// Search until told to stop, ignoring any other limit. Use [`Engine::stop`](crate::Engine::stop)
+    /// to end the search.
+    #[must_use]
+    pub fn infinite(mut self) -> SearchParams {
+        self.infinite = true;
+        self
+    }
+
+    /// Search on the engine's predicted move for the opponent. Use [`Engine::ponderhit`]
+    /// if the prediction turns out to be correct, to turn this into a normal search.
+    ///
+    /// [`Engine::ponderhit`]: crate::Engine::ponderhit
+    #[must_use]
+    pub fn ponder(mut self) -> SearchParams {
+        self.ponder = true;
+        self
+    }
+
+    pub(crate) fn to_command(&self) -> String {
+        let mut cmd = String::from("go");
+
+        if let Some(wtime) = self.wtime {
+            cmd.push_str(&format!(" wtime {wtime}"));
+        }
+        if let Some(btime) = self.btime {
+            cmd.push_str(&format!(" btime {btime}"));
+        }
+        if let Some(winc) = self.winc {
+            cmd.push_str(&format!(" winc {winc}"));
+        }
+        if let Some(binc) = self.binc {
+            cmd.push_str(&format!(" binc {binc}"));
+        }
+        if let Some(movestogo) = self.movestogo {
+            cmd.push_str(&format!(" movestogo {movestogo}"));
+        }
+        if let Some(depth) = self.depth {
+            cmd.push_str(&format!(" depth {depth}"));
+        }
+        if let Some(nodes) = self.nodes {
+            cmd.push_str(&format!(" nodes {nodes}"));
+        }
+        if let Some(mate) = self.mate {
+            cmd.push_str(&format!(" mate {mate}"));
+        }
+        if let Some(movetime) = self.movetime {
+            cmd.push_str(&format!(" movetime {movetime}"));
+        }
+        if self.infinite {
+            cmd.push_str(" infinite");
+        }
+        if self.ponder {
+            cmd.push_str(" ponder");
+        }
+
+        cmd
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_params_send_plain_go() {
+        assert_eq!(SearchParams::new().to_command(), "go");
+    }
+
+    #[test]
+    fn only_set_fields_are_included_in_order() {
+        let params = SearchParams::new()
+            .wtime(300_000)
+            .btime(300_000)
+            .winc(2_000)
+            .binc(2_000)
+            .movestogo(40);
+        assert_eq!(
+            params.to_command(),
+            "go wtime 300000 btime 300000 winc 2000 binc 2000 movestogo 40"
+        );
+    }
+
+    #[test]
+    fn depth_nodes_and_mate_searches() {
+        assert_eq!(SearchParams::new().depth(10).to_command(), "go depth 10");
+        assert_eq!(SearchParams::new().nodes(1_000_000).to_command(), "go nodes 1000000");
+        assert_eq!(SearchParams::new().mate(3).to_command(), "go mate 3");
+        assert_eq!(SearchParams::new().movetime(5_000).to_command(), "go movetime 5000");
+    }
+
+    #[test]
+    fn infinite_and_ponder_flags() {
+        assert_eq!(SearchParams::new().infinite().to_command(), "go infinite");
+        assert_eq!(
+            SearchParams::new().depth(10).ponder().to_command(),
+            "go depth 10 ponder"
+        );
+    }
+}